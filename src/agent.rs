@@ -0,0 +1,206 @@
+use bevy::prelude::*;
+use oxidized_navigation::query::{find_path, perform_string_pulling_on_path};
+use oxidized_navigation::{NavMesh, NavMeshSettings};
+
+#[cfg(feature = "rapier")]
+use bevy_rapier3d::prelude::Collider;
+#[cfg(feature = "rapier")]
+use parry3d::math::Isometry;
+#[cfg(feature = "rapier")]
+use parry3d::shape::Shape;
+
+#[cfg(feature = "rapier")]
+use crate::avoidance::{steer_around_obstacles, AvoidanceSettings, DynamicObstacle};
+
+/// How far the target has to move from where a path was last planned before
+/// the cached path is thrown away and recomputed.
+const REPLAN_DISTANCE_THRESHOLD: f32 = 1.0;
+
+/// An entity that walks itself along a string-pulled nav mesh path toward
+/// `target`, replanning whenever the nav mesh's tile count changes or the
+/// target drifts too far from the last planned position. A rebake that keeps
+/// the tile count the same does not trigger a replan.
+#[derive(Component)]
+pub struct NavAgent {
+    pub target: Vec3,
+    pub speed: f32,
+    pub arrival_radius: f32,
+    path: Vec<Vec3>,
+    current_waypoint: usize,
+    planned_for: Option<Vec3>,
+}
+
+impl NavAgent {
+    pub fn new(target: Vec3, speed: f32, arrival_radius: f32) -> Self {
+        Self {
+            target,
+            speed,
+            arrival_radius,
+            path: Vec::new(),
+            current_waypoint: 0,
+            planned_for: None,
+        }
+    }
+
+    fn needs_replan(&self) -> bool {
+        match self.planned_for {
+            Some(planned_for) => planned_for.distance(self.target) > REPLAN_DISTANCE_THRESHOLD,
+            None => true,
+        }
+    }
+}
+
+/// Replans the cached path for any [`NavAgent`] whose nav mesh is stale or
+/// whose target has moved too far from where the path was last planned.
+///
+/// `NavMesh`'s tiles are mutated through an internal `Arc<RwLock<_>>` by the
+/// plugin's background bake tasks rather than through `ResMut`, so
+/// `Res<NavMesh>::is_changed` never flips after the resource is first
+/// inserted. The tile count is used as a cheap proxy for "the mesh grew"
+/// instead.
+pub fn replan_agent_paths(
+    nav_mesh_settings: Res<NavMeshSettings>,
+    nav_mesh: Res<NavMesh>,
+    mut last_tile_count: Local<usize>,
+    mut agents: Query<(&GlobalTransform, &mut NavAgent)>,
+) {
+    let nav_mesh_tiles = nav_mesh.get();
+    let Ok(nav_mesh) = nav_mesh_tiles.read() else {
+        return;
+    };
+
+    let tile_count = nav_mesh.get_tiles().len();
+    let nav_mesh_changed = tile_count != *last_tile_count;
+    *last_tile_count = tile_count;
+
+    if !nav_mesh_changed && !agents.iter().any(|(_, agent)| agent.needs_replan()) {
+        return;
+    }
+
+    for (transform, mut agent) in &mut agents {
+        if !nav_mesh_changed && !agent.needs_replan() {
+            continue;
+        }
+
+        let start_pos = transform.translation();
+        let end_pos = agent.target;
+
+        match find_path(&nav_mesh, &nav_mesh_settings, start_pos, end_pos, None) {
+            Ok(path) => match perform_string_pulling_on_path(&nav_mesh, start_pos, end_pos, &path)
+            {
+                Ok(string_path) => {
+                    agent.path = string_path;
+                    agent.current_waypoint = 0;
+                    agent.planned_for = Some(end_pos);
+                }
+                Err(error) => error!("Error with string path: {:?}", error),
+            },
+            Err(error) => error!("Error with pathfinding: {:?}", error),
+        }
+    }
+}
+
+/// Steers each [`NavAgent`]'s `Transform` toward its current waypoint,
+/// advancing to the next one once within `arrival_radius`. Nearby
+/// [`DynamicObstacle`] colliders nudge the step direction away via
+/// [`steer_around_obstacles`], so agents don't clip through dynamic obstacles
+/// the baked nav mesh doesn't know about. This is deliberately *not*
+/// `NavMeshAffector`: affectors are the static geometry the nav mesh bake
+/// already routes paths around.
+#[cfg(feature = "rapier")]
+pub fn move_agents(
+    time: Res<Time>,
+    avoidance_settings: Res<AvoidanceSettings>,
+    obstacles: Query<(&Collider, &GlobalTransform), With<DynamicObstacle>>,
+    mut agents: Query<(&mut Transform, &mut NavAgent)>,
+) {
+    for (mut transform, mut agent) in &mut agents {
+        let Some(&waypoint) = agent.path.get(agent.current_waypoint) else {
+            continue;
+        };
+
+        let to_waypoint = waypoint - transform.translation;
+        let distance = to_waypoint.length();
+
+        if distance <= agent.arrival_radius {
+            if agent.current_waypoint + 1 < agent.path.len() {
+                agent.current_waypoint += 1;
+            }
+            continue;
+        }
+
+        let desired_velocity = to_waypoint.normalize() * agent.speed;
+        let nearby_obstacles: Vec<(&dyn Shape, Isometry<f32>)> = obstacles
+            .iter()
+            .filter(|(_, obstacle_transform)| {
+                obstacle_transform
+                    .translation()
+                    .distance(transform.translation)
+                    <= avoidance_settings.lookahead_radius
+            })
+            .map(|(collider, obstacle_transform)| {
+                let obstacle_transform = obstacle_transform.compute_transform();
+                let iso = Isometry::new(
+                    obstacle_transform.translation.into(),
+                    obstacle_transform.rotation.to_scaled_axis().into(),
+                );
+                (<&dyn Shape>::from(collider), iso)
+            })
+            .collect();
+
+        let steered_velocity = steer_around_obstacles(
+            transform.translation,
+            desired_velocity,
+            &nearby_obstacles,
+            &avoidance_settings,
+        );
+
+        let step = agent.speed * time.delta_seconds();
+        transform.translation += steered_velocity.normalize_or_zero() * step.min(distance);
+    }
+}
+
+/// Steers each [`NavAgent`]'s `Transform` toward its current waypoint,
+/// advancing to the next one once within `arrival_radius`.
+#[cfg(not(feature = "rapier"))]
+pub fn move_agents(time: Res<Time>, mut agents: Query<(&mut Transform, &mut NavAgent)>) {
+    for (mut transform, mut agent) in &mut agents {
+        let Some(&waypoint) = agent.path.get(agent.current_waypoint) else {
+            continue;
+        };
+
+        let to_waypoint = waypoint - transform.translation;
+        let distance = to_waypoint.length();
+
+        if distance <= agent.arrival_radius {
+            if agent.current_waypoint + 1 < agent.path.len() {
+                agent.current_waypoint += 1;
+            }
+            continue;
+        }
+
+        let step = agent.speed * time.delta_seconds();
+        transform.translation += to_waypoint.normalize() * step.min(distance);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fresh_agent_needs_replan() {
+        let agent = NavAgent::new(Vec3::new(1.0, 0.0, 0.0), 1.0, 0.1);
+        assert!(agent.needs_replan());
+    }
+
+    #[test]
+    fn agent_does_not_need_replan_until_target_drifts_past_the_threshold() {
+        let mut agent = NavAgent::new(Vec3::ZERO, 1.0, 0.1);
+        agent.planned_for = Some(Vec3::ZERO);
+        assert!(!agent.needs_replan());
+
+        agent.target = Vec3::new(REPLAN_DISTANCE_THRESHOLD * 2.0, 0.0, 0.0);
+        assert!(agent.needs_replan());
+    }
+}
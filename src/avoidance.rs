@@ -0,0 +1,151 @@
+//! Local dynamic-obstacle avoidance layered on top of the baked nav mesh.
+//! Pathfinding only replans when the nav mesh itself rebuilds, so this
+//! steers around obstacles the nav mesh doesn't know about (movers, other
+//! agents) by nudging the goal-seeking velocity away from nearby collider
+//! surfaces found via parry closest-point queries.
+
+use bevy::prelude::*;
+use parry3d::math::Isometry;
+use parry3d::query::{self, ClosestPoints};
+use parry3d::shape::{Ball, Shape};
+
+/// Marks a collider as a dynamic obstacle for [`steer_around_obstacles`] to
+/// react to — a mover, another agent, anything the baked nav mesh doesn't
+/// know about. Deliberately distinct from `oxidized_navigation`'s
+/// `NavMeshAffector`: affectors are the static geometry the nav mesh bake
+/// already routes paths around, so feeding them into avoidance as well
+/// would just re-avoid ground truth the path already accounts for instead
+/// of the obstacles the nav mesh can't see.
+#[derive(Component, Default)]
+pub struct DynamicObstacle;
+
+/// Tuning for [`steer_around_obstacles`].
+#[derive(Resource, Clone, Copy)]
+pub struct AvoidanceSettings {
+    /// Obstacles farther than this from the agent are ignored.
+    pub lookahead_radius: f32,
+    /// How strongly the repulsion vector is blended into the desired
+    /// velocity.
+    pub repulsion_strength: f32,
+}
+
+impl Default for AvoidanceSettings {
+    fn default() -> Self {
+        Self {
+            lookahead_radius: 3.0,
+            repulsion_strength: 1.0,
+        }
+    }
+}
+
+/// Adjusts `desired_velocity` to steer an agent at `agent_position` away
+/// from nearby `obstacles`, blending a repulsion vector from the closest
+/// surface point of each obstacle within `settings.lookahead_radius` with
+/// the original goal-seeking direction.
+///
+/// Obstacles whose closest point coincides with the agent's position have
+/// no well-defined repulsion normal and are skipped, so the agent falls
+/// back to the pure goal direction for that obstacle instead of steering
+/// off in an arbitrary direction.
+pub fn steer_around_obstacles(
+    agent_position: Vec3,
+    desired_velocity: Vec3,
+    obstacles: &[(&dyn Shape, Isometry<f32>)],
+    settings: &AvoidanceSettings,
+) -> Vec3 {
+    let agent_shape = Ball::new(f32::EPSILON);
+    let agent_iso = Isometry::translation(agent_position.x, agent_position.y, agent_position.z);
+
+    let mut repulsion = Vec3::ZERO;
+
+    for (obstacle_shape, obstacle_iso) in obstacles {
+        let closest = query::closest_points(
+            &agent_iso,
+            &agent_shape,
+            obstacle_iso,
+            *obstacle_shape,
+            settings.lookahead_radius,
+        );
+
+        let Ok(ClosestPoints::WithinMargin(agent_point, obstacle_point)) = closest else {
+            continue;
+        };
+
+        let away = Vec3::new(
+            agent_point.x - obstacle_point.x,
+            agent_point.y - obstacle_point.y,
+            agent_point.z - obstacle_point.z,
+        );
+        let distance = away.length();
+        if distance <= f32::EPSILON {
+            continue;
+        }
+
+        let falloff = (1.0 - distance / settings.lookahead_radius).max(0.0);
+        repulsion += away.normalize() * falloff;
+    }
+
+    desired_velocity + repulsion * settings.repulsion_strength
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parry3d::shape::Cuboid;
+
+    fn settings() -> AvoidanceSettings {
+        AvoidanceSettings {
+            lookahead_radius: 3.0,
+            repulsion_strength: 1.0,
+        }
+    }
+
+    #[test]
+    fn no_obstacles_leaves_desired_velocity_unchanged() {
+        let desired_velocity = Vec3::new(1.0, 0.0, 0.0);
+        let steered = steer_around_obstacles(Vec3::ZERO, desired_velocity, &[], &settings());
+        assert_eq!(steered, desired_velocity);
+    }
+
+    #[test]
+    fn obstacle_outside_lookahead_radius_is_ignored() {
+        let obstacle = Cuboid::new(Vec3::new(0.5, 0.5, 0.5).into());
+        let far_iso = Isometry::translation(10.0, 0.0, 0.0);
+        let obstacles: [(&dyn Shape, Isometry<f32>); 1] = [(&obstacle, far_iso)];
+
+        let desired_velocity = Vec3::new(1.0, 0.0, 0.0);
+        let steered =
+            steer_around_obstacles(Vec3::ZERO, desired_velocity, &obstacles, &settings());
+        assert_eq!(steered, desired_velocity);
+    }
+
+    #[test]
+    fn nearby_obstacle_repels_away_from_its_surface() {
+        let obstacle = Cuboid::new(Vec3::new(0.5, 0.5, 0.5).into());
+        let obstacle_iso = Isometry::translation(2.0, 0.0, 0.0);
+        let obstacles: [(&dyn Shape, Isometry<f32>); 1] = [(&obstacle, obstacle_iso)];
+
+        let desired_velocity = Vec3::new(1.0, 0.0, 0.0);
+        let steered =
+            steer_around_obstacles(Vec3::ZERO, desired_velocity, &obstacles, &settings());
+
+        // The obstacle sits ahead on +X, so the repulsion should push back
+        // toward -X relative to just going straight for it.
+        assert!(steered.x < desired_velocity.x);
+    }
+
+    #[test]
+    fn obstacle_overlapping_agent_has_no_normal_and_is_skipped() {
+        // The agent sits inside the obstacle, so there's no well-defined
+        // closest-surface-point normal; the obstacle should be ignored
+        // rather than steer the agent arbitrarily.
+        let obstacle = Ball::new(1.0);
+        let obstacle_iso = Isometry::translation(0.0, 0.0, 0.0);
+        let obstacles: [(&dyn Shape, Isometry<f32>); 1] = [(&obstacle, obstacle_iso)];
+
+        let desired_velocity = Vec3::new(1.0, 0.0, 0.0);
+        let steered =
+            steer_around_obstacles(Vec3::ZERO, desired_velocity, &obstacles, &settings());
+        assert_eq!(steered, desired_velocity);
+    }
+}
@@ -0,0 +1,23 @@
+//! Converts a [`Mesh`] into a trimesh collider for whichever physics backend
+//! is enabled via cargo features, so `read_colliders` doesn't have to care
+//! which one is in use. `rapier` is the only backend today - see the
+//! `avian` comment in Cargo.toml for why there isn't an avian one yet.
+
+use bevy::prelude::*;
+
+#[cfg(feature = "rapier")]
+mod rapier {
+    use super::*;
+    use bevy_rapier3d::prelude::*;
+
+    pub fn mesh_to_collider(mesh: &Mesh) -> Collider {
+        Collider::from_bevy_mesh(mesh, &ComputedColliderShape::TriMesh)
+            .expect("collider mesh should be convertible to a trimesh")
+    }
+}
+
+#[cfg(not(feature = "rapier"))]
+compile_error!("enable the rapier feature (avian isn't supported yet - see Cargo.toml)");
+
+#[cfg(feature = "rapier")]
+pub use rapier::mesh_to_collider;
@@ -0,0 +1,187 @@
+//! Turns a string-pulled path into turn-by-turn compass instructions, for
+//! accessibility narration or an AI director that needs cardinal-direction
+//! guidance instead of raw waypoints.
+
+use bevy::prelude::*;
+use std::f32::consts::TAU;
+
+/// An 8-way compass direction, quantized from a path segment's bearing on
+/// the XZ plane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompassOctant {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl CompassOctant {
+    /// Quantizes an `atan2(dz, dx)` bearing in radians into the nearest
+    /// octant.
+    fn from_bearing(bearing: f32) -> Self {
+        const OCTANTS: [CompassOctant; 8] = [
+            CompassOctant::E,
+            CompassOctant::NE,
+            CompassOctant::N,
+            CompassOctant::NW,
+            CompassOctant::W,
+            CompassOctant::SW,
+            CompassOctant::S,
+            CompassOctant::SE,
+        ];
+        let normalized = bearing.rem_euclid(TAU);
+        let index = (normalized / (TAU / 8.0)).round() as usize % 8;
+        OCTANTS[index]
+    }
+}
+
+/// A single turn-by-turn instruction derived from one or more consecutive
+/// path segments that share a compass octant.
+#[derive(Debug, Clone, Copy)]
+pub struct NavInstruction {
+    pub octant: CompassOctant,
+    /// Total length of the segment(s) this instruction covers, in world
+    /// units.
+    pub length: f32,
+    /// Signed turn angle in radians between this instruction's first segment
+    /// and the segment before it, positive for a left turn, negative for a
+    /// right turn. `0.0` for the first instruction.
+    pub turn_angle: f32,
+}
+
+/// Converts a string-pulled path into a sequence of compass-direction
+/// instructions, collapsing consecutive segments that share an octant into
+/// one instruction with summed length.
+pub fn path_to_directions(path: &[Vec3]) -> Vec<NavInstruction> {
+    let mut instructions: Vec<NavInstruction> = Vec::new();
+    let mut previous_bearing: Option<f32> = None;
+
+    for (a, b) in path.iter().zip(path.iter().skip(1)) {
+        let delta = *b - *a;
+        let length = (delta.x * delta.x + delta.z * delta.z).sqrt();
+        if length == 0.0 {
+            continue;
+        }
+
+        let bearing = delta.z.atan2(delta.x);
+        let octant = CompassOctant::from_bearing(bearing);
+        let turn_angle = match previous_bearing {
+            Some(previous_bearing) => wrap_angle(bearing - previous_bearing),
+            None => 0.0,
+        };
+        previous_bearing = Some(bearing);
+
+        match instructions.last_mut() {
+            Some(last) if last.octant == octant => {
+                last.length += length;
+            }
+            _ => instructions.push(NavInstruction {
+                octant,
+                length,
+                turn_angle,
+            }),
+        }
+    }
+
+    instructions
+}
+
+/// Wraps an angle in radians into `(-PI, PI]`.
+fn wrap_angle(angle: f32) -> f32 {
+    let wrapped = (angle + std::f32::consts::PI).rem_euclid(TAU) - std::f32::consts::PI;
+    if wrapped == -std::f32::consts::PI {
+        std::f32::consts::PI
+    } else {
+        wrapped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    #[test]
+    fn from_bearing_snaps_cardinal_directions() {
+        assert_eq!(CompassOctant::from_bearing(0.0), CompassOctant::E);
+        assert_eq!(CompassOctant::from_bearing(PI / 2.0), CompassOctant::N);
+        assert_eq!(CompassOctant::from_bearing(PI), CompassOctant::W);
+        assert_eq!(CompassOctant::from_bearing(-PI / 2.0), CompassOctant::S);
+    }
+
+    #[test]
+    fn from_bearing_rounds_to_nearest_octant_at_boundary() {
+        // Exactly halfway between E and NE should round up to NE.
+        assert_eq!(CompassOctant::from_bearing(PI / 8.0), CompassOctant::NE);
+    }
+
+    #[test]
+    fn from_bearing_wraps_negative_bearings() {
+        assert_eq!(CompassOctant::from_bearing(-TAU), CompassOctant::from_bearing(0.0));
+    }
+
+    #[test]
+    fn wrap_angle_keeps_small_angles_unchanged() {
+        assert_eq!(wrap_angle(0.5), 0.5);
+    }
+
+    #[test]
+    fn wrap_angle_wraps_past_positive_pi() {
+        let wrapped = wrap_angle(PI + 0.1);
+        assert!((wrapped - (-PI + 0.1)).abs() < 1e-5);
+    }
+
+    #[test]
+    fn wrap_angle_wraps_past_negative_pi() {
+        let wrapped = wrap_angle(-PI - 0.1);
+        assert!((wrapped - (PI - 0.1)).abs() < 1e-5);
+    }
+
+    #[test]
+    fn wrap_angle_keeps_exact_pi_positive() {
+        assert_eq!(wrap_angle(PI), PI);
+    }
+
+    #[test]
+    fn path_to_directions_collapses_segments_sharing_an_octant() {
+        let path = [
+            Vec3::new(0.0, 0.0, 0.0),
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(2.0, 0.0, 0.0),
+        ];
+        let instructions = path_to_directions(&path);
+        assert_eq!(instructions.len(), 1);
+        assert_eq!(instructions[0].octant, CompassOctant::E);
+        assert!((instructions[0].length - 2.0).abs() < 1e-5);
+        assert_eq!(instructions[0].turn_angle, 0.0);
+    }
+
+    #[test]
+    fn path_to_directions_emits_a_new_instruction_on_turn() {
+        let path = [
+            Vec3::new(0.0, 0.0, 0.0),
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(1.0, 0.0, 1.0),
+        ];
+        let instructions = path_to_directions(&path);
+        assert_eq!(instructions.len(), 2);
+        assert_eq!(instructions[0].octant, CompassOctant::E);
+        assert_eq!(instructions[1].octant, CompassOctant::N);
+        assert!((instructions[1].turn_angle - PI / 2.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn path_to_directions_skips_zero_length_segments() {
+        let path = [
+            Vec3::new(0.0, 0.0, 0.0),
+            Vec3::new(0.0, 0.0, 0.0),
+            Vec3::new(1.0, 0.0, 0.0),
+        ];
+        let instructions = path_to_directions(&path);
+        assert_eq!(instructions.len(), 1);
+    }
+}
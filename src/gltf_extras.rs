@@ -0,0 +1,146 @@
+//! Reads nav-mesh affector tags and [`NavMeshSettings`] overrides out of
+//! glTF node `extras`, in place of matching on node names.
+
+use bevy::gltf::GltfExtras;
+use bevy::prelude::*;
+use oxidized_navigation::{NavMeshAffector, NavMeshSettings};
+use serde::Deserialize;
+
+/// Name of the scene-level node whose extras override [`NavMeshSettings`].
+const SETTINGS_NODE_NAME: &str = "NavMeshSettings";
+
+// Per-object area/cost typing (e.g. "this node is slow terrain") was also
+// requested alongside `nav_affector`, but oxidized_navigation 0.1.1 has no
+// area- or cost-typing mechanism for its navmesh at all - there's nothing
+// for a per-node cost to feed into. Deliberately not implemented; revisit if
+// a later oxidized_navigation release adds area/cost support.
+#[derive(Deserialize, Default)]
+struct NavNodeExtras {
+    #[serde(default)]
+    nav_affector: bool,
+}
+
+#[derive(Deserialize, Default)]
+struct NavMeshSettingsExtras {
+    cell_width: Option<f32>,
+    cell_height: Option<f32>,
+    walkable_radius: Option<u16>,
+    step_height: Option<u16>,
+    max_traversable_slope_degrees: Option<f32>,
+}
+
+/// Tags mesh-bearing entities as [`NavMeshAffector`]s based on their glTF
+/// node extras, replacing the old `Name` substring check. The entity's
+/// first mesh-holding child is consumed into a physics collider the same
+/// way the name-based version did.
+pub fn read_colliders(
+    mut commands: Commands,
+    added_extras: Query<(Entity, &GltfExtras, &Children), Added<GltfExtras>>,
+    meshes: Res<Assets<Mesh>>,
+    mesh_handles: Query<&Handle<Mesh>>,
+) {
+    for (entity, extras, children) in &added_extras {
+        let Ok(extras) = serde_json::from_str::<NavNodeExtras>(&extras.value) else {
+            continue;
+        };
+
+        if !extras.nav_affector {
+            continue;
+        }
+
+        let colliders: Vec<_> = children
+            .iter()
+            .filter_map(|entity| mesh_handles.get(*entity).ok().map(|mesh| (*entity, mesh)))
+            .collect();
+        let Some((collider_entity, collider_mesh_handle)) = colliders.first() else {
+            continue;
+        };
+        let collider_mesh = meshes.get(collider_mesh_handle).unwrap();
+        commands.entity(*collider_entity).despawn_recursive();
+
+        let collider = crate::collider_backend::mesh_to_collider(collider_mesh);
+        commands
+            .entity(entity)
+            .insert((collider, NavMeshAffector::default()));
+    }
+}
+
+/// Overrides [`NavMeshSettings`] fields from the scene-level
+/// [`SETTINGS_NODE_NAME`] node's glTF extras, letting level designers tune
+/// navmesh generation from Blender instead of editing Rust.
+pub fn apply_nav_mesh_settings_from_extras(
+    mut nav_mesh_settings: ResMut<NavMeshSettings>,
+    added_extras: Query<(&Name, &GltfExtras), Added<GltfExtras>>,
+) {
+    for (name, extras) in &added_extras {
+        if name.as_str() != SETTINGS_NODE_NAME {
+            continue;
+        }
+
+        let Ok(overrides) = serde_json::from_str::<NavMeshSettingsExtras>(&extras.value) else {
+            continue;
+        };
+
+        if let Some(cell_width) = overrides.cell_width {
+            nav_mesh_settings.cell_width = cell_width;
+        }
+        if let Some(cell_height) = overrides.cell_height {
+            nav_mesh_settings.cell_height = cell_height;
+        }
+        if let Some(walkable_radius) = overrides.walkable_radius {
+            nav_mesh_settings.walkable_radius = walkable_radius;
+        }
+        if let Some(step_height) = overrides.step_height {
+            nav_mesh_settings.step_height = step_height;
+        }
+        if let Some(slope_degrees) = overrides.max_traversable_slope_degrees {
+            nav_mesh_settings.max_traversable_slope_radians = slope_degrees.to_radians();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nav_node_extras_defaults_nav_affector_to_false() {
+        let extras: NavNodeExtras = serde_json::from_str("{}").unwrap();
+        assert!(!extras.nav_affector);
+    }
+
+    #[test]
+    fn nav_node_extras_reads_nav_affector() {
+        let extras: NavNodeExtras = serde_json::from_str(r#"{"nav_affector": true}"#).unwrap();
+        assert!(extras.nav_affector);
+    }
+
+    #[test]
+    fn nav_node_extras_rejects_malformed_json() {
+        assert!(serde_json::from_str::<NavNodeExtras>("not json").is_err());
+    }
+
+    #[test]
+    fn nav_mesh_settings_extras_defaults_all_fields_to_none() {
+        let extras: NavMeshSettingsExtras = serde_json::from_str("{}").unwrap();
+        assert_eq!(extras.cell_width, None);
+        assert_eq!(extras.cell_height, None);
+        assert_eq!(extras.walkable_radius, None);
+        assert_eq!(extras.step_height, None);
+        assert_eq!(extras.max_traversable_slope_degrees, None);
+    }
+
+    #[test]
+    fn nav_mesh_settings_extras_reads_provided_fields() {
+        let extras: NavMeshSettingsExtras =
+            serde_json::from_str(r#"{"cell_width": 0.5, "walkable_radius": 3}"#).unwrap();
+        assert_eq!(extras.cell_width, Some(0.5));
+        assert_eq!(extras.walkable_radius, Some(3));
+        assert_eq!(extras.cell_height, None);
+    }
+
+    #[test]
+    fn nav_mesh_settings_extras_rejects_malformed_json() {
+        assert!(serde_json::from_str::<NavMeshSettingsExtras>("not json").is_err());
+    }
+}
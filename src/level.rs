@@ -0,0 +1,201 @@
+//! Multi-level streaming: loads one glTF level at a time and swaps it for
+//! the next when a [`LevelTraveler`] enters a [`LevelTransition`] zone.
+//!
+//! Known gap: despawning the old scene's nav-mesh affectors only stops them
+//! from contributing to *future* tile rebuilds; `oxidized_navigation` 0.1's
+//! tile tracking reacts to `Changed<GlobalTransform>`/`Changed<Collider>`,
+//! not to component removal, and `TileAffectors`/`DirtyTiles`/
+//! `GenerationTicker` aren't `pub` in 0.1.1, so there is no public hook here
+//! to mark the old level's already-baked tiles dirty or cancel a bake still
+//! in flight for them. Concretely: an agent can still be routed across the
+//! old level's geometry until the new level's affectors happen to overlap
+//! and overwrite those tiles. `check_level_transitions` logs a `warn!` on
+//! every transition so this is visible at runtime rather than silent. Fixing
+//! it for real needs a newer `oxidized_navigation` that exposes tile
+//! invalidation; until then, this module should be treated as "single
+//! persistent level plus cosmetic streaming" rather than a guarantee that
+//! stale geometry stops being walkable.
+
+use bevy::gltf::{Gltf, GltfExtras};
+use bevy::prelude::*;
+use oxidized_navigation::NavMeshAffector;
+use serde::Deserialize;
+
+/// The currently active (or loading) level. `generation` is bumped on every
+/// transition; each spawned scene root is tagged with the generation it was
+/// spawned for (see [`LevelRoot`]). Nothing reads it to discard nav mesh
+/// bakes still in flight for an unloaded level (see the module docs).
+#[derive(Resource)]
+pub struct NavLevel {
+    pub handle: Handle<Gltf>,
+    pub generation: u32,
+    spawned_generation: Option<u32>,
+}
+
+impl NavLevel {
+    pub fn new(handle: Handle<Gltf>) -> Self {
+        Self {
+            handle,
+            generation: 0,
+            spawned_generation: None,
+        }
+    }
+}
+
+/// Marks the root entity of a streamed-in level's scene so it (and its
+/// affector children) can be torn down wholesale on a level transition.
+#[derive(Component)]
+pub struct LevelRoot {
+    pub generation: u32,
+}
+
+/// A trigger volume that streams in `next_level_path` once a
+/// [`LevelTraveler`] comes within `trigger_radius` of it.
+#[derive(Component)]
+pub struct LevelTransition {
+    pub next_level_path: String,
+    pub trigger_radius: f32,
+}
+
+/// Marker for the entity (typically the player or camera rig) whose
+/// position is checked against [`LevelTransition`] zones.
+#[derive(Component)]
+pub struct LevelTraveler;
+
+#[derive(Deserialize)]
+struct LevelTransitionExtras {
+    next_level_path: String,
+    #[serde(default = "default_trigger_radius")]
+    trigger_radius: f32,
+}
+
+fn default_trigger_radius() -> f32 {
+    2.0
+}
+
+/// Tags nodes authored with a `next_level_path` glTF extra as
+/// [`LevelTransition`] zones, the same way [`gltf_extras::read_colliders`]
+/// tags nav-mesh affectors from their own extras.
+///
+/// [`gltf_extras::read_colliders`]: crate::gltf_extras::read_colliders
+pub fn read_level_transitions(
+    mut commands: Commands,
+    added_extras: Query<(Entity, &GltfExtras), Added<GltfExtras>>,
+) {
+    for (entity, extras) in &added_extras {
+        let Ok(extras) = serde_json::from_str::<LevelTransitionExtras>(&extras.value) else {
+            continue;
+        };
+
+        commands.entity(entity).insert(LevelTransition {
+            next_level_path: extras.next_level_path,
+            trigger_radius: extras.trigger_radius,
+        });
+    }
+}
+
+/// Spawns the active level's scene once its glTF has finished loading,
+/// tagging the root with the level's current generation.
+pub fn spawn_level(
+    mut commands: Commands,
+    gltf_assets: Res<Assets<Gltf>>,
+    mut nav_level: ResMut<NavLevel>,
+) {
+    if nav_level.spawned_generation == Some(nav_level.generation) {
+        return;
+    }
+
+    let Some(gltf) = gltf_assets.get(&nav_level.handle) else {
+        return;
+    };
+
+    info!("spawned level (generation {})", nav_level.generation);
+    commands.spawn((
+        SceneBundle {
+            scene: gltf.scenes[0].clone(),
+            ..default()
+        },
+        LevelRoot {
+            generation: nav_level.generation,
+        },
+    ));
+    nav_level.spawned_generation = Some(nav_level.generation);
+}
+
+/// Checks [`LevelTraveler`]s against [`LevelTransition`] zones and, on
+/// entry, despawns the current level's scene and its nav-mesh affectors
+/// before streaming in the next level, logging a `warn!` about the stale
+/// baked tiles this leaves behind. See the module docs for why this doesn't
+/// rebuild or cancel the old level's already-baked tiles.
+pub fn check_level_transitions(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut nav_level: ResMut<NavLevel>,
+    transitions: Query<(&GlobalTransform, &LevelTransition)>,
+    travelers: Query<&GlobalTransform, With<LevelTraveler>>,
+    level_roots: Query<(Entity, &LevelRoot)>,
+    affectors: Query<Entity, With<NavMeshAffector>>,
+) {
+    for traveler_transform in &travelers {
+        for (zone_transform, transition) in &transitions {
+            let distance = traveler_transform
+                .translation()
+                .distance(zone_transform.translation());
+            if distance > transition.trigger_radius {
+                continue;
+            }
+
+            for (entity, root) in &level_roots {
+                if root.generation == nav_level.generation {
+                    commands.entity(entity).despawn_recursive();
+                }
+            }
+            for affector in &affectors {
+                commands.entity(affector).despawn_recursive();
+            }
+            warn!(
+                "transitioning from level generation {} to {}: the old level's baked nav mesh \
+                 tiles are not invalidated (see module docs) and may still be walkable until \
+                 the new level's affectors overwrite them",
+                nav_level.generation,
+                nav_level.generation + 1
+            );
+
+            nav_level.generation += 1;
+            nav_level.handle = asset_server.load(&transition.next_level_path);
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn level_transition_extras_defaults_trigger_radius() {
+        let extras: LevelTransitionExtras =
+            serde_json::from_str(r#"{"next_level_path": "next.glb"}"#).unwrap();
+        assert_eq!(extras.next_level_path, "next.glb");
+        assert_eq!(extras.trigger_radius, default_trigger_radius());
+    }
+
+    #[test]
+    fn level_transition_extras_reads_provided_trigger_radius() {
+        let extras: LevelTransitionExtras =
+            serde_json::from_str(r#"{"next_level_path": "next.glb", "trigger_radius": 5.0}"#)
+                .unwrap();
+        assert_eq!(extras.trigger_radius, 5.0);
+    }
+
+    #[test]
+    fn level_transition_extras_requires_next_level_path() {
+        assert!(serde_json::from_str::<LevelTransitionExtras>(r#"{"trigger_radius": 5.0}"#)
+            .is_err());
+    }
+
+    #[test]
+    fn level_transition_extras_rejects_malformed_json() {
+        assert!(serde_json::from_str::<LevelTransitionExtras>("not json").is_err());
+    }
+}
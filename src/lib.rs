@@ -0,0 +1,6 @@
+pub mod agent;
+pub mod avoidance;
+pub mod collider_backend;
+pub mod directions;
+pub mod gltf_extras;
+pub mod level;
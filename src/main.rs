@@ -1,11 +1,17 @@
-use bevy::gltf::Gltf;
 use bevy::prelude::*;
 use bevy::window::PresentMode;
 use bevy_editor_pls::prelude::*;
 use bevy_prototype_debug_lines::{DebugLines, DebugLinesPlugin};
+#[cfg(feature = "rapier")]
 use bevy_rapier3d::prelude::*;
+use navigation_mcve::agent::{move_agents, replan_agent_paths, NavAgent};
+use navigation_mcve::avoidance;
+use navigation_mcve::gltf_extras;
+use navigation_mcve::level::{
+    check_level_transitions, read_level_transitions, spawn_level, LevelTraveler, NavLevel,
+};
 use oxidized_navigation::query::{find_path, perform_string_pulling_on_path};
-use oxidized_navigation::{NavMesh, NavMeshAffector, NavMeshSettings, OxidizedNavigationPlugin};
+use oxidized_navigation::{NavMesh, NavMeshSettings, OxidizedNavigationPlugin};
 use smooth_bevy_cameras::{
     controllers::unreal::{UnrealCameraBundle, UnrealCameraController, UnrealCameraPlugin},
     LookTransformPlugin,
@@ -14,57 +20,73 @@ use std::f32::consts::TAU;
 use std::iter;
 
 fn main() {
-    App::new()
-        .add_plugins(DefaultPlugins.set(WindowPlugin {
-            window: WindowDescriptor {
-                width: 800.,
-                height: 600.,
-                title: "Bevy game".to_string(),
-                canvas: Some("#bevy".to_owned()),
-                present_mode: PresentMode::AutoVsync,
-                ..default()
-            },
+    let mut app = App::new();
+    app.add_plugins(DefaultPlugins.set(WindowPlugin {
+        window: WindowDescriptor {
+            width: 800.,
+            height: 600.,
+            title: "Bevy game".to_string(),
+            canvas: Some("#bevy".to_owned()),
+            present_mode: PresentMode::AutoVsync,
             ..default()
-        }))
-        .add_plugin(DebugLinesPlugin::default())
-        .add_plugin(OxidizedNavigationPlugin)
-        .add_plugin(EditorPlugin)
-        .add_plugin(LookTransformPlugin)
-        .add_plugin(UnrealCameraPlugin::default())
-        .add_plugin(RapierPhysicsPlugin::<NoUserData>::default())
+        },
+        ..default()
+    }))
+    .add_plugin(DebugLinesPlugin::default())
+    .add_plugin(OxidizedNavigationPlugin)
+    .add_plugin(EditorPlugin)
+    .add_plugin(LookTransformPlugin)
+    .add_plugin(UnrealCameraPlugin::default());
+
+    #[cfg(feature = "rapier")]
+    app.add_plugin(RapierPhysicsPlugin::<NoUserData>::default())
         .add_plugin(RapierDebugRenderPlugin::default())
-        .insert_resource(RapierConfiguration::default())
-        .insert_resource(NavMeshSettings {
-            cell_width: 0.25,
-            cell_height: 0.1,
-            tile_width: 100,
-            world_half_extents: 250.0,
-            world_bottom_bound: -10.0,
-            max_traversable_slope_radians: (40.0_f32 - 0.1).to_radians(),
-            walkable_height: 20,
-            walkable_radius: 2,
-            step_height: 3,
-            min_region_area: 100,
-            merge_region_area: 500,
-            max_contour_simplification_error: 0.5,
-            max_edge_length: 80,
-        })
-        .insert_resource(Msaa { samples: 4 })
-        .insert_resource(ClearColor(Color::rgb(0.4, 0.4, 0.4)))
-        .add_startup_system(setup)
-        .add_system(spawn_scene)
-        .add_system(draw_nav_mesh)
-        .add_system(read_colliders)
-        .add_system(navigate)
-        .run();
+        .insert_resource(RapierConfiguration::default());
+
+    app.insert_resource(NavMeshSettings {
+        cell_width: 0.25,
+        cell_height: 0.1,
+        tile_width: 100,
+        world_half_extents: 250.0,
+        world_bottom_bound: -10.0,
+        max_traversable_slope_radians: (40.0_f32 - 0.1).to_radians(),
+        walkable_height: 20,
+        walkable_radius: 2,
+        step_height: 3,
+        min_region_area: 100,
+        merge_region_area: 500,
+        max_contour_simplification_error: 0.5,
+        max_edge_length: 80,
+    })
+    .insert_resource(avoidance::AvoidanceSettings::default())
+    .insert_resource(Msaa { samples: 4 })
+    .insert_resource(ClearColor(Color::rgb(0.4, 0.4, 0.4)))
+    .add_startup_system(setup)
+    .add_system(spawn_level)
+    .add_system(read_level_transitions)
+    .add_system(check_level_transitions)
+    .add_system(draw_nav_mesh)
+    .add_system(gltf_extras::read_colliders)
+    .add_system(gltf_extras::apply_nav_mesh_settings_from_extras)
+    .add_system(navigate)
+    .add_system(replan_agent_paths)
+    .add_system(move_agents.after(replan_agent_paths))
+    .run();
 }
 
-#[derive(Resource)]
-struct ToSpawn(Handle<Gltf>);
+/// Where the demo [`NavAgent`] spawned below walks to: the ground point
+/// directly below the camera's starting position. `navigate`'s debug line
+/// instead follows the camera's current position.
+const DEMO_AGENT_TARGET: Vec3 = Vec3::new(-2.0, 0.0, 5.0);
 
-fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
     let gltf_handle = asset_server.load("level.glb");
-    commands.insert_resource(ToSpawn(gltf_handle));
+    commands.insert_resource(NavLevel::new(gltf_handle));
 
     commands
         .spawn(Camera3dBundle::default())
@@ -72,77 +94,49 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
             UnrealCameraController::default(),
             Vec3::new(-2.0, 5.0, 5.0),
             Vec3::new(0., 0., 0.),
-        ));
-}
+            Vec3::Y,
+        ))
+        .insert(LevelTraveler);
 
-fn spawn_scene(
-    mut commands: Commands,
-    gltf_assets: Res<Assets<Gltf>>,
-    to_spawn: Option<Res<ToSpawn>>,
-) {
-    if let Some(to_spawn) = to_spawn {
-        if let Some(gltf) = gltf_assets.get(&to_spawn.0) {
-            info!("spawned level");
-            commands.spawn((SceneBundle {
-                scene: gltf.scenes[0].clone(),
-                ..default()
-            },));
-            commands.remove_resource::<ToSpawn>();
+    // A standalone cube that walks itself to `DEMO_AGENT_TARGET` along the
+    // baked nav mesh, so the NavAgent/replan/move pipeline is actually
+    // exercised instead of only existing as unit-tested logic.
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Cube { size: 0.5 })),
+            material: materials.add(Color::rgb(0.8, 0.2, 0.2).into()),
+            ..default()
+        },
+        Name::new("NavAgent demo cube"),
+        NavAgent::new(DEMO_AGENT_TARGET, 2.0, 0.25),
+    ));
 
-            const HALF_SIZE: f32 = 50.0;
-            commands.spawn((
-                DirectionalLightBundle {
-                    directional_light: DirectionalLight {
-                        // Configure the projection to better fit the scene
-                        shadow_projection: OrthographicProjection {
-                            left: -HALF_SIZE,
-                            right: HALF_SIZE,
-                            bottom: -HALF_SIZE,
-                            top: HALF_SIZE,
-                            near: -10.0 * HALF_SIZE,
-                            far: 10.0 * HALF_SIZE,
-                            ..default()
-                        },
-                        shadows_enabled: true,
-                        ..default()
-                    },
-                    transform: Transform {
-                        translation: Vec3::new(0.0, 2.0, 0.0),
-                        rotation: Quat::from_rotation_x(-TAU / 8.),
-                        ..default()
-                    },
+    const HALF_SIZE: f32 = 50.0;
+    commands.spawn((
+        DirectionalLightBundle {
+            directional_light: DirectionalLight {
+                // Configure the projection to better fit the scene
+                shadow_projection: OrthographicProjection {
+                    left: -HALF_SIZE,
+                    right: HALF_SIZE,
+                    bottom: -HALF_SIZE,
+                    top: HALF_SIZE,
+                    near: -10.0 * HALF_SIZE,
+                    far: 10.0 * HALF_SIZE,
                     ..default()
                 },
-                Name::new("Light"),
-            ));
-        }
-    }
-}
-
-pub fn read_colliders(
-    mut commands: Commands,
-    added_name: Query<(Entity, &Name, &Children), Added<Name>>,
-    meshes: Res<Assets<Mesh>>,
-    mesh_handles: Query<&Handle<Mesh>>,
-) {
-    for (entity, name, children) in &added_name {
-        if name.to_lowercase().contains("collider") {
-            let colliders: Vec<_> = children
-                .iter()
-                .filter_map(|entity| mesh_handles.get(*entity).ok().map(|mesh| (*entity, mesh)))
-                .collect();
-            let (collider_entity, collider_mesh_handle) = colliders.first().unwrap();
-            let collider_mesh = meshes.get(collider_mesh_handle).unwrap();
-            commands.entity(*collider_entity).despawn_recursive();
-
-            let rapier_collider =
-                Collider::from_bevy_mesh(collider_mesh, &ComputedColliderShape::TriMesh).unwrap();
-
-            commands
-                .entity(entity)
-                .insert((rapier_collider, NavMeshAffector::default()));
-        }
-    }
+                shadows_enabled: true,
+                ..default()
+            },
+            transform: Transform {
+                translation: Vec3::new(0.0, 2.0, 0.0),
+                rotation: Quat::from_rotation_x(-TAU / 8.),
+                ..default()
+            },
+            ..default()
+        },
+        Name::new("Light"),
+    ));
 }
 
 fn navigate(
@@ -162,7 +156,7 @@ fn navigate(
                 match perform_string_pulling_on_path(&nav_mesh, start_pos, end_pos, &path) {
                     Ok(string_path) => {
                         let path = iter::once(start_pos)
-                            .chain(string_path.into_iter())
+                            .chain(string_path)
                             .chain(iter::once(end_pos));
                         for (a, b) in path.clone().zip(path.skip(1)) {
                             lines.line_colored(a, b, 0., Color::RED);